@@ -0,0 +1,157 @@
+//! An interactive Python console that evaluates expressions against the loaded
+//! waveform and renders the result next to it. The interpreter's globals are
+//! kept alive between evaluations, so a session behaves like a notebook kernel:
+//! variables defined in one cell are visible in the next.
+//!
+//! Scripts reach back into the UI through the [`ScriptHandle`] exposed in the
+//! `surfer` pymodule (see [`crate::translation::pytranslator`]). It enqueues the
+//! same [`Message`]s the rest of the app uses, so a script can add signals, set
+//! the active scope, or zoom the view to fit the whole trace without touching
+//! internals. The handle has no call that zooms to a given time range.
+
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+
+use pyo3::exceptions::PySyntaxError;
+use pyo3::types::{PyDict, PyModule};
+use pyo3::{pyclass, pymethods, IntoPy, Py, PyObject, Python};
+
+use crate::{Message, ScopeDescriptor, SignalDescriptor};
+
+/// The sender scripts push [`Message`]s onto. Set once, when the console is
+/// created, so the pyo3-exposed [`ScriptHandle`] can reach the app's queue.
+static SCRIPT_SENDER: Mutex<Option<Sender<Message>>> = Mutex::new(None);
+
+fn enqueue(message: Message) {
+    if let Some(sender) = SCRIPT_SENDER.lock().unwrap().as_ref() {
+        let _ = sender.send(message);
+    }
+}
+
+/// The scripting handle, exposed as the `Surfer` class of the `surfer` pymodule.
+/// The console binds a ready-made instance as `surfer`. Every method
+/// enqueues a [`Message`] rather than mutating state directly, so scripts go
+/// through the same path as the command prompt and the remote-control server.
+#[pyclass(name = "Surfer")]
+#[derive(Clone)]
+pub struct ScriptHandle;
+
+#[pymethods]
+impl ScriptHandle {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// Add a signal to the view by name.
+    fn add_signal(&self, name: &str) {
+        enqueue(Message::AddSignal(SignalDescriptor::Name(name.to_string())));
+    }
+
+    /// Make `name` the active scope.
+    fn set_active_scope(&self, name: &str) {
+        enqueue(Message::SetActiveScope(ScopeDescriptor::Name(name.to_string())));
+    }
+
+    /// Zoom so the whole trace fits the viewport.
+    fn zoom_fit(&self) {
+        enqueue(Message::ZoomToFit);
+    }
+}
+
+/// How an evaluated value is shown in the console. Plain values become text;
+/// objects exposing `_repr_png_` or `_repr_markdown_` render as an inline image
+/// or markdown, and a sequence of string pairs renders as a table.
+pub enum ConsoleOutput {
+    /// The value's `repr`, the captured error, or empty text for a cell that
+    /// was run as statements.
+    Text(String),
+    /// A list of `(time, value)` string rows, e.g. a signal's transitions.
+    Table(Vec<(String, String)>),
+    /// Raw image bytes (PNG) from an object exposing `_repr_png_`.
+    Image(Vec<u8>),
+    /// Markdown source from an object exposing `_repr_markdown_`.
+    Markdown(String),
+}
+
+/// A long-lived Python interpreter session. `globals` persists across every
+/// call to [`Self::eval`], giving scripts notebook-kernel semantics.
+pub struct PythonConsole {
+    globals: Py<PyDict>,
+    /// The history of evaluated cells and what they rendered to.
+    pub cells: Vec<(String, ConsoleOutput)>,
+}
+
+impl PythonConsole {
+    /// Create a console wired to the app's message queue.
+    pub fn new(sender: Sender<Message>) -> Self {
+        *SCRIPT_SENDER.lock().unwrap() = Some(sender);
+
+        let globals = Python::with_gil(|py| {
+            let globals = PyDict::new(py);
+            // Bind a ready-made handle as `surfer` so scripts can call e.g.
+            // `surfer.add_signal(...)` directly, and keep the pymodule itself
+            // reachable as `surfer_module` for its classes.
+            let _ = globals.set_item("surfer", ScriptHandle::new().into_py(py));
+            if let Ok(module) = PyModule::import(py, "surfer") {
+                let _ = globals.set_item("surfer_module", module);
+            }
+            globals.into()
+        });
+
+        Self {
+            globals,
+            cells: vec![],
+        }
+    }
+
+    /// Evaluate a cell against the persistent interpreter state, classify the
+    /// result into a [`ConsoleOutput`], and record it in the history.
+    pub fn eval(&mut self, source: &str) -> &ConsoleOutput {
+        let output = Python::with_gil(|py| {
+            let globals = self.globals.as_ref(py);
+            // Try as an expression so the value can be rendered. A `SyntaxError`
+            // means the source is not an expression (e.g. an assignment), so run
+            // it as a statement block instead; any other error is a genuine
+            // runtime failure and is reported as-is — re-running the source would
+            // duplicate the side effects it already performed.
+            match py.eval(source, Some(globals), None) {
+                Ok(value) => render(py, value.into()),
+                Err(e) if e.is_instance_of::<PySyntaxError>(py) => {
+                    match py.run(source, Some(globals), None) {
+                        Ok(()) => ConsoleOutput::Text(String::new()),
+                        Err(e) => ConsoleOutput::Text(format!("{e}")),
+                    }
+                }
+                Err(e) => ConsoleOutput::Text(format!("{e}")),
+            }
+        });
+
+        self.cells.push((source.to_string(), output));
+        &self.cells.last().unwrap().1
+    }
+}
+
+/// Pick the richest rendering an object opts into, falling back to its `repr`.
+fn render(py: Python, value: PyObject) -> ConsoleOutput {
+    let obj = value.as_ref(py);
+
+    if let Ok(png) = obj.call_method0("_repr_png_") {
+        if let Ok(bytes) = png.extract::<Vec<u8>>() {
+            return ConsoleOutput::Image(bytes);
+        }
+    }
+    if let Ok(md) = obj.call_method0("_repr_markdown_") {
+        if let Ok(text) = md.extract::<String>() {
+            return ConsoleOutput::Markdown(text);
+        }
+    }
+    if let Ok(rows) = obj.extract::<Vec<(String, String)>>() {
+        return ConsoleOutput::Table(rows);
+    }
+
+    match obj.repr() {
+        Ok(repr) => ConsoleOutput::Text(repr.to_string_lossy().into_owned()),
+        Err(e) => ConsoleOutput::Text(format!("{e}")),
+    }
+}
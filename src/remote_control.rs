@@ -0,0 +1,189 @@
+//! A line-oriented JSON-RPC server that lets external tools — a VS Code or
+//! Emacs companion plugin, say — drive Surfer the way a language server is
+//! driven by its client. It reuses the [`Message`] enum as its command
+//! vocabulary instead of duplicating logic: every method understood here maps
+//! onto one of the same messages the fuzzy command prompt builds in
+//! [`crate::commands`].
+//!
+//! Messages are framed like the Language Server Protocol: a `Content-Length: N`
+//! header, terminated by a blank line, followed by exactly `N` bytes of UTF-8
+//! JSON. Requests are dispatched by their `method` string, the resulting
+//! [`Message`] is pushed onto the application's queue, and a response keyed by
+//! the original `id` is written back.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use color_eyre::{
+    eyre::{bail, eyre, WrapErr},
+    Result,
+};
+use serde_json::{json, Value};
+
+use crate::{Message, ScopeDescriptor, SignalDescriptor};
+
+/// Where the remote-control server listens for clients.
+pub enum RemoteControl {
+    /// Talk JSON-RPC over the process's own stdin/stdout.
+    Stdio,
+    /// Accept TCP clients on the given address, one connection at a time.
+    Tcp(String),
+}
+
+impl RemoteControl {
+    /// Spawn the server on a background thread. Decoded requests are turned
+    /// into [`Message`]s and forwarded on `sender`; the thread runs until the
+    /// transport is closed.
+    pub fn spawn(self, sender: Sender<Message>) -> thread::JoinHandle<Result<()>> {
+        thread::spawn(move || match self {
+            RemoteControl::Stdio => {
+                let stdin = io::stdin();
+                let stdout = io::stdout();
+                serve(stdin.lock(), stdout.lock(), &sender)
+            }
+            RemoteControl::Tcp(addr) => {
+                let listener = TcpListener::bind(&addr)
+                    .with_context(|| format!("Failed to bind remote control to {addr}"))?;
+                for stream in listener.incoming() {
+                    let stream = stream.context("Failed to accept remote control client")?;
+                    let reader = BufReader::new(stream.try_clone()?);
+                    // A client error should not bring the whole server down.
+                    if let Err(e) = serve(reader, stream, &sender) {
+                        log::warn!("Remote control client disconnected: {e:#}");
+                    }
+                }
+                Ok(())
+            }
+        })
+    }
+}
+
+/// Drive a single connection to completion, reading framed requests until the
+/// reader reaches end of input.
+fn serve(mut reader: impl BufRead, mut writer: impl Write, sender: &Sender<Message>) -> Result<()> {
+    while let Some(body) = read_message(&mut reader)? {
+        let response = handle(&body, sender);
+        write_message(&mut writer, &response)?;
+    }
+    Ok(())
+}
+
+/// Read one `Content-Length`-framed message, or `None` at end of input.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<String>> {
+    let mut content_length = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let line = header.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .context("Invalid Content-Length header")?,
+                );
+            }
+        }
+    }
+
+    let length = content_length.ok_or_else(|| eyre!("Message is missing a Content-Length header"))?;
+    let mut buf = vec![0u8; length];
+    reader
+        .read_exact(&mut buf)
+        .context("Truncated remote control message body")?;
+    Ok(Some(String::from_utf8(buf).context("Message body was not UTF-8")?))
+}
+
+/// Serialize and frame a response.
+fn write_message(writer: &mut impl Write, response: &Value) -> Result<()> {
+    let body = serde_json::to_string(response)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Parse a request body, dispatch it, and build the response object keyed by
+/// the request's `id`. Malformed requests are answered with an error object.
+fn handle(body: &str, sender: &Sender<Message>) -> Value {
+    let request: Value = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(e) => return error(Value::Null, -32700, &format!("Parse error: {e}")),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str);
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    match method {
+        Some(method) => match message_from_request(method, &params) {
+            Ok(message) => {
+                if sender.send(message).is_err() {
+                    error(id, -32603, "Surfer is shutting down")
+                } else {
+                    json!({ "jsonrpc": "2.0", "id": id, "result": null })
+                }
+            }
+            Err(e) => error(id, -32602, &format!("{e:#}")),
+        },
+        None => error(id, -32600, "Request is missing a method"),
+    }
+}
+
+/// Build a JSON-RPC error response.
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Map a method name and its parameters onto a [`Message`]. The method names
+/// match the verbs of the fuzzy command parser in [`crate::commands`].
+fn message_from_request(method: &str, params: &Value) -> Result<Message> {
+    let message = match method {
+        "load_vcd" => Message::LoadVcd(string_param(params, "path")?.into()),
+        "load_url" => Message::LoadVcdFromUrl(string_param(params, "url")?),
+        "config_reload" => Message::ReloadConfig,
+        "scroll_to_start" => Message::ScrollToStart,
+        "scroll_to_end" => Message::ScrollToEnd,
+        "zoom_in" => Message::CanvasZoom {
+            mouse_ptr_timestamp: None,
+            delta: 0.5,
+        },
+        "zoom_out" => Message::CanvasZoom {
+            mouse_ptr_timestamp: None,
+            delta: 2.0,
+        },
+        "zoom_fit" => Message::ZoomToFit,
+        "toggle_menu" => Message::ToggleMenu,
+        "toggle_fullscreen" => Message::ToggleFullscreen,
+        "module_add" => Message::AddScope(ScopeDescriptor::Name(string_param(params, "name")?)),
+        "module_select" => {
+            Message::SetActiveScope(ScopeDescriptor::Name(string_param(params, "name")?))
+        }
+        "signal_add" => Message::AddSignal(SignalDescriptor::Name(string_param(params, "name")?)),
+        "signal_set_color" => {
+            Message::SignalColorChange(None, string_param(params, "color")?)
+        }
+        _ => bail!("Unknown method: {method}"),
+    };
+    Ok(message)
+}
+
+/// Pull a required string parameter out of the `params` object.
+fn string_param(params: &Value, key: &str) -> Result<String> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("Missing or non-string parameter `{key}`"))
+}
@@ -0,0 +1,144 @@
+//! A caching, multi-threaded front end to the translators. Translating a value
+//! — especially through [`PyTranslator`], which serializes on the GIL — is too
+//! expensive to redo on every redraw and zoom. This subsystem precomputes the
+//! translations of the visible signals on a background thread pool and keeps
+//! the results in an LRU cache keyed by `(signal_idx, value)`, so scrolling
+//! across a long trace full of decoded buses stays responsive.
+//!
+//! Native Rust translators are fanned out across the pool. [`PyTranslator`] is
+//! handled specially: a window of pending pairs is batched into a single
+//! `Python::with_gil` call via [`PyTranslator::translate_batch`], so the GIL is
+//! acquired once per batch rather than once per value.
+
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use fastwave_backend::{Signal, SignalIdx, SignalValue};
+use lru::LruCache;
+use threadpool::ThreadPool;
+
+use super::pytranslator::PyTranslator;
+use super::{TranslationResult, Translator};
+
+/// The cache key. The value is kept in its rendered bit-string form so it is
+/// cheap to hash and compare, and identical across the `BigUint`/`String`
+/// representations of the same bits.
+type TranslationKey = (SignalIdx, String);
+
+fn value_key(signal: &Signal, value: &SignalValue) -> String {
+    match value {
+        SignalValue::BigUint(val) => {
+            format!("{val:0width$b}", width = signal.num_bits().unwrap_or(0) as usize)
+        }
+        SignalValue::String(val) => val.clone(),
+    }
+}
+
+/// A thread pool plus an LRU cache of translated values.
+pub struct TranslationCache {
+    pool: ThreadPool,
+    cache: Arc<Mutex<LruCache<TranslationKey, TranslationResult>>>,
+    /// Keys currently being translated on the pool, so the same value is not
+    /// dispatched twice when it appears more than once in a batch or across
+    /// overlapping `prefetch` calls.
+    in_flight: Arc<Mutex<HashSet<TranslationKey>>>,
+}
+
+impl TranslationCache {
+    /// Create a cache with room for `capacity` translated values, backed by a
+    /// pool sized to the number of available CPUs.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            pool: ThreadPool::new(num_cpus::get()),
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+            in_flight: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Drop every cached translation. Called when a new VCD is loaded, since
+    /// signal indices no longer refer to the same signals.
+    pub fn invalidate(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Atomically claim a key for translation: returns `true` if the value is
+    /// neither cached nor already being translated, marking it in-flight. The
+    /// cache lock is held across the in-flight check so the decision cannot
+    /// race a concurrent [`Self::reserve`].
+    fn reserve(&self, key: &TranslationKey) -> bool {
+        let cache = self.cache.lock().unwrap();
+        if cache.contains(key) {
+            return false;
+        }
+        self.in_flight.lock().unwrap().insert(key.clone())
+    }
+
+    /// Look up an already-translated value, bumping it to most-recently-used.
+    pub fn get(&self, idx: SignalIdx, signal: &Signal, value: &SignalValue) -> Option<TranslationResult> {
+        self.cache
+            .lock()
+            .unwrap()
+            .get(&(idx, value_key(signal, value)))
+            .cloned()
+    }
+
+    /// Translate the visible values of a native Rust translator, fanning the
+    /// work out across the pool and caching each result. Already-cached values
+    /// are skipped.
+    pub fn prefetch<T>(&self, translator: Arc<T>, idx: SignalIdx, signal: Arc<Signal>, values: Vec<SignalValue>)
+    where
+        T: Translator + Send + Sync + 'static,
+    {
+        for value in values {
+            let key = (idx, value_key(&signal, &value));
+            if !self.reserve(&key) {
+                continue;
+            }
+
+            let translator = Arc::clone(&translator);
+            let signal = Arc::clone(&signal);
+            let cache = Arc::clone(&self.cache);
+            let in_flight = Arc::clone(&self.in_flight);
+            self.pool.execute(move || {
+                if let Ok(result) = translator.translate(&signal, &value) {
+                    cache.lock().unwrap().put(key.clone(), result);
+                }
+                in_flight.lock().unwrap().remove(&key);
+            });
+        }
+    }
+
+    /// Translate a window of values through a [`PyTranslator`] in one batched
+    /// GIL acquisition, caching each result. The batch is dispatched onto the
+    /// pool just like [`Self::prefetch`], so a large pending window does not
+    /// block the caller; values already cached or in flight are skipped.
+    pub fn prefetch_python(&self, translator: Arc<PyTranslator>, idx: SignalIdx, signal: Arc<Signal>, values: Vec<SignalValue>) {
+        let pending: Vec<(String, SignalValue)> = values
+            .into_iter()
+            .map(|value| (value_key(&signal, &value), value))
+            .filter(|(key, _)| self.reserve(&(idx, key.clone())))
+            .collect();
+
+        if pending.is_empty() {
+            return;
+        }
+
+        let cache = Arc::clone(&self.cache);
+        let in_flight = Arc::clone(&self.in_flight);
+        self.pool.execute(move || {
+            let batch: Vec<(&Signal, &SignalValue)> =
+                pending.iter().map(|(_, value)| (signal.as_ref(), value)).collect();
+            if let Ok(results) = translator.translate_batch(&batch) {
+                let mut cache = cache.lock().unwrap();
+                for ((key, _), result) in pending.iter().zip(results) {
+                    cache.put((idx, key.clone()), result);
+                }
+            }
+            let mut in_flight = in_flight.lock().unwrap();
+            for (key, _) in &pending {
+                in_flight.remove(&(idx, key.clone()));
+            }
+        });
+    }
+}
@@ -6,7 +6,7 @@ use color_eyre::{
 use fastwave_backend::{Signal, SignalValue};
 use pyo3::{types::PyModule, PyObject, Python, ToPyObject, PyResult, pymodule, pyfunction, wrap_pyfunction, pyclass, pymethods};
 
-use super::{TranslationResult, Translator};
+use super::{SignalInfo, TranslationResult, Translator};
 
 pub struct PyTranslator {
     name: String,
@@ -64,26 +64,76 @@ impl Translator for PyTranslator {
     }
 
     fn translate(&self, signal: &Signal, value: &SignalValue) -> Result<TranslationResult> {
-        let value_str = match value {
-            SignalValue::BigUint(val) => format!(
-                "{val:0width$b}",
-                width = signal.num_bits().unwrap_or(0) as usize
-            ),
-            SignalValue::String(val) => val.clone(),
-        };
+        let meta = PySignalMeta::new(signal, value);
 
         Python::with_gil(|py| {
             let result = self
                 .instance
-                .call_method1(py, "translate", (signal.name(), value_str))
-                .with_context(|| format!("Failed to run translates on {}", self.name))?;
+                .call_method1(py, "translate", (signal.name(), meta))
+                .with_context(|| format!("Failed to run translate on {}", self.name))?;
 
             let val: PyTranslationResult = result.extract(py)?;
             Ok(val.0)
         })
     }
+
+    fn signal_info(&self, signal: &Signal, name: &str) -> Result<SignalInfo> {
+        Python::with_gil(|py| {
+            let result = self
+                .instance
+                .call_method1(py, "signal_info", (name,))
+                .with_context(|| format!("Failed to run signal_info on {}", self.name))?;
+
+            let info: PySignalInfo = result.extract(py)?;
+            Ok(info.0)
+        })
+        .with_context(|| format!("While querying layout of {}", signal.name()))
+    }
+}
+
+impl PyTranslator {
+    /// Translate a window of `(signal, value)` pairs under a single GIL
+    /// acquisition. If the plugin provides an optional `translate_batch`
+    /// method it is handed the whole window at once, amortizing the cost of
+    /// acquiring the GIL; otherwise each pair falls back to `translate`. Both
+    /// paths hand the plugin the same `(name, SignalMeta)` arguments as the
+    /// non-batched [`Translator::translate`], so a value decodes identically
+    /// however it reaches a translator.
+    pub fn translate_batch(&self, batch: &[(&Signal, &SignalValue)]) -> Result<Vec<TranslationResult>> {
+        Python::with_gil(|py| {
+            let pairs: Vec<(String, PySignalMeta)> = batch
+                .iter()
+                .map(|(signal, value)| (signal.name(), PySignalMeta::new(signal, value)))
+                .collect();
+
+            if self.instance.as_ref(py).hasattr("translate_batch")? {
+                let result = self
+                    .instance
+                    .call_method1(py, "translate_batch", (pairs,))
+                    .with_context(|| format!("Failed to run translate_batch on {}", self.name))?;
+
+                let results: Vec<PyTranslationResult> = result.extract(py)?;
+                Ok(results.into_iter().map(|r| r.0).collect())
+            } else {
+                pairs
+                    .into_iter()
+                    .map(|(name, meta)| {
+                        let result = self
+                            .instance
+                            .call_method1(py, "translate", (name, meta))
+                            .with_context(|| format!("Failed to run translate on {}", self.name))?;
+                        let val: PyTranslationResult = result.extract(py)?;
+                        Ok(val.0)
+                    })
+                    .collect()
+            }
+        })
+    }
 }
 
+/// A translated value handed back to Surfer. Python plugins construct this
+/// from their `translate` method; nested `subfields` let a struct or bus be
+/// decomposed into named fields recursively.
 #[pyclass(name = "TranslationResult")]
 #[derive(Clone)]
 struct PyTranslationResult (TranslationResult);
@@ -91,14 +141,75 @@ struct PyTranslationResult (TranslationResult);
 #[pymethods]
 impl PyTranslationResult {
     #[new]
-    fn new(val_str: &str) -> Self {
+    #[args(subfields = "None")]
+    fn new(val: &str, subfields: Option<Vec<(String, PyTranslationResult)>>) -> Self {
         Self(TranslationResult {
-            val: val_str.to_string(),
-            subfields: vec![]
+            val: val.to_string(),
+            subfields: subfields
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, field)| (name, field.0))
+                .collect(),
         })
     }
 }
 
+/// The field layout of a signal, advertised once via `signal_info` rather than
+/// rebuilt for every value. Mirrors [`PyTranslationResult`] but without values.
+#[pyclass(name = "SignalInfo")]
+#[derive(Clone)]
+struct PySignalInfo (SignalInfo);
+
+#[pymethods]
+impl PySignalInfo {
+    #[new]
+    #[args(subfields = "None")]
+    fn new(subfields: Option<Vec<(String, PySignalInfo)>>) -> Self {
+        match subfields {
+            Some(fields) if !fields.is_empty() => Self(SignalInfo::Compound {
+                subfields: fields
+                    .into_iter()
+                    .map(|(name, field)| (name, field.0))
+                    .collect(),
+            }),
+            _ => Self(SignalInfo::Bits),
+        }
+    }
+}
+
+/// A signal value passed into `translate`, carrying the metadata a plugin would
+/// otherwise have to recover by parsing a zero-padded string: the declared
+/// width, the raw bit vector, and whether any bit is `x`/`z`.
+#[pyclass(name = "SignalMeta")]
+#[derive(Clone)]
+struct PySignalMeta {
+    #[pyo3(get)]
+    num_bits: u32,
+    #[pyo3(get)]
+    bits: String,
+    #[pyo3(get)]
+    has_xz: bool,
+}
+
+impl PySignalMeta {
+    fn new(signal: &Signal, value: &SignalValue) -> Self {
+        let num_bits = signal.num_bits().unwrap_or(0);
+        let bits = match value {
+            SignalValue::BigUint(val) => format!("{val:0width$b}", width = num_bits as usize),
+            SignalValue::String(val) => val.clone(),
+        };
+        let has_xz = bits
+            .bytes()
+            .any(|b| matches!(b, b'x' | b'X' | b'z' | b'Z'));
+
+        Self {
+            num_bits,
+            bits,
+            has_xz,
+        }
+    }
+}
+
 #[pyfunction]
 fn test() {
     println!("test")
@@ -113,6 +224,9 @@ fn test() {
 #[pymodule]
 pub fn surfer(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyTranslationResult>()?;
+    m.add_class::<PySignalInfo>()?;
+    m.add_class::<PySignalMeta>()?;
+    m.add_class::<crate::python_console::ScriptHandle>()?;
     m.add_function(wrap_pyfunction!(test, m)?)?;
     Ok(())
 }